@@ -1,14 +1,119 @@
+// Now that `cevtib` itself is `#![no_std]`, this example is a genuine
+// freestanding binary rather than a std binary that merely imports a
+// no_std-flavored library: it supplies its own panic handler, global
+// allocator, entry point, and the `mem*` intrinsics a hosted libc would
+// otherwise provide.
 #![no_std]
+#![no_main]
 
-use cevtib;
+extern crate alloc;
 
-fn main() {
-    let mut b = cevtib::BitVec::new();
+use core::alloc::{GlobalAlloc, Layout};
+use core::arch::asm;
+use core::cell::UnsafeCell;
+use core::panic::PanicInfo;
+use core::ptr;
+
+use cevtib::BitVec;
+
+/// A single-arena bump allocator; bare-metal targets have no libc
+/// allocator, so a `#[global_allocator]` is required for `cevtib`'s
+/// heap fallback to have anywhere to allocate.
+struct BumpAllocator {
+    arena: UnsafeCell<[u8; 4096]>,
+    offset: UnsafeCell<usize>,
+}
+
+unsafe impl Sync for BumpAllocator {}
+
+unsafe impl GlobalAlloc for BumpAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let arena_ptr = self.arena.get() as *mut u8;
+        let offset = &mut *self.offset.get();
+        let start = arena_ptr.add(*offset);
+        let new_offset = *offset + start.align_offset(layout.align()) + layout.size();
+
+        if new_offset > 4096 {
+            return ptr::null_mut();
+        }
+
+        let allocated = arena_ptr.add(new_offset - layout.size());
+        *offset = new_offset;
+        allocated
+    }
+
+    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {
+        // Bump allocators never free; fine for a short-lived example.
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: BumpAllocator = BumpAllocator {
+    arena: UnsafeCell::new([0; 4096]),
+    offset: UnsafeCell::new(0),
+};
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    loop {}
+}
+
+// Required because liballoc is prebuilt with unwinding support even
+// though this binary compiles with `panic = "abort"`.
+#[no_mangle]
+pub extern "C" fn rust_eh_personality() {}
+
+#[no_mangle]
+pub unsafe extern "C" fn memset(s: *mut u8, c: i32, n: usize) -> *mut u8 {
+    for i in 0..n {
+        *s.add(i) = c as u8;
+    }
+    s
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn memcpy(dest: *mut u8, src: *const u8, n: usize) -> *mut u8 {
+    for i in 0..n {
+        *dest.add(i) = *src.add(i);
+    }
+    dest
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn memmove(dest: *mut u8, src: *const u8, n: usize) -> *mut u8 {
+    if (dest as *const u8) <= src {
+        return memcpy(dest, src, n);
+    }
+    // `dest` lies after `src`: copy backwards so overlapping bytes are
+    // read before they are overwritten.
+    for i in (0..n).rev() {
+        *dest.add(i) = *src.add(i);
+    }
+    dest
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn memcmp(s1: *const u8, s2: *const u8, n: usize) -> i32 {
+    for i in 0..n {
+        let (a, b) = (*s1.add(i), *s2.add(i));
+        if a != b {
+            return a as i32 - b as i32;
+        }
+    }
+    0
+}
+
+#[no_mangle]
+pub extern "C" fn _start() -> ! {
+    let mut b = BitVec::<u8>::new();
 
     for i in 0..10 {
         b.push(i % 2 == 0);
     }
 
-    assert_eq!(Some(true), b.get(2));
-    assert_eq!(Some(false), b.get(3));
+    let ok = b.get(2) == Some(true) && b.get(3) == Some(false);
+
+    unsafe {
+        asm!("syscall", in("rax") 60, in("rdi") if ok { 0 } else { 1 }, options(noreturn));
+    }
 }
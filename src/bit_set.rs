@@ -0,0 +1,197 @@
+use core::cmp;
+
+use crate::{from_stores, BitStore, BitVec};
+
+/// A set of non-negative integers backed by a [`BitVec`], where bit
+/// index `i` being set means `i` is a member of the set.
+#[derive(Debug, PartialEq)]
+pub struct BitSet<B> {
+    bits: BitVec<B>,
+}
+
+impl<B: BitStore> Default for BitSet<B> {
+    fn default() -> Self {
+        BitSet::new()
+    }
+}
+
+impl<B: BitStore> BitSet<B> {
+    pub fn new() -> BitSet<B> {
+        BitSet {
+            bits: BitVec::new(),
+        }
+    }
+
+    /// Whether `i` is a member of the set.
+    pub fn contains(&self, i: usize) -> bool {
+        self.bits.get(i).unwrap_or(false)
+    }
+
+    /// Add `i` to the set, growing the underlying bitvec if needed.
+    pub fn insert(&mut self, i: usize) {
+        while self.bits.len() <= i {
+            self.bits.push(false);
+        }
+
+        self.bits.set_unchecked(i, true);
+    }
+
+    /// Remove `i` from the set. A no-op if `i` was never a member.
+    pub fn remove(&mut self, i: usize) {
+        if i < self.bits.len() {
+            self.bits.set_unchecked(i, false);
+        }
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        BitSet {
+            bits: combine(&self.bits, &other.bits, |a, b| a | b),
+        }
+    }
+
+    pub fn union_with(&mut self, other: &Self) {
+        self.bits = combine(&self.bits, &other.bits, |a, b| a | b);
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        BitSet {
+            bits: combine(&self.bits, &other.bits, |a, b| a & b),
+        }
+    }
+
+    pub fn intersection_with(&mut self, other: &Self) {
+        self.bits = combine(&self.bits, &other.bits, |a, b| a & b);
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        BitSet {
+            bits: combine(&self.bits, &other.bits, |a, b| a & !b),
+        }
+    }
+
+    pub fn difference_with(&mut self, other: &Self) {
+        self.bits = combine(&self.bits, &other.bits, |a, b| a & !b);
+    }
+
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        BitSet {
+            bits: combine(&self.bits, &other.bits, |a, b| (a | b) & !(a & b)),
+        }
+    }
+
+    pub fn symmetric_difference_with(&mut self, other: &Self) {
+        self.bits = combine(&self.bits, &other.bits, |a, b| (a | b) & !(a & b));
+    }
+
+    /// Whether every member of `self` is also a member of `other`.
+    pub fn is_subset(&self, other: &Self) -> bool {
+        each_store_pair(&self.bits, &other.bits, |a, b| (a & !b) == B::zero())
+    }
+
+    /// Whether `self` and `other` share no members.
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        each_store_pair(&self.bits, &other.bits, |a, b| (a & b) == B::zero())
+    }
+}
+
+/// Number of `B`-sized stores needed to cover `len` bits.
+fn num_stores<B: BitStore>(len: usize) -> usize {
+    len.div_ceil(B::bits())
+}
+
+/// The `index`-th store of `v`, or zero past its end. Relies on
+/// `BitVec` keeping the bits past `len()` zeroed in its final store.
+fn store_or_zero<B: BitStore>(v: &BitVec<B>, index: usize) -> B {
+    v.iter_stores().nth(index).unwrap_or_else(B::zero)
+}
+
+/// Apply `f` to every corresponding pair of stores in `a` and `b`,
+/// zero-extending the shorter one, requiring `f` to hold for all of them.
+fn each_store_pair<B: BitStore>(
+    a: &BitVec<B>,
+    b: &BitVec<B>,
+    f: impl Fn(B, B) -> bool,
+) -> bool {
+    let stores = cmp::max(num_stores::<B>(a.len()), num_stores::<B>(b.len()));
+
+    (0..stores).all(|i| f(store_or_zero(a, i), store_or_zero(b, i)))
+}
+
+/// Combine `a` and `b` word-by-word with `f`, zero-extending the
+/// shorter one, producing a new bitvec as long as the longer input.
+fn combine<B: BitStore>(a: &BitVec<B>, b: &BitVec<B>, f: impl Fn(B, B) -> B) -> BitVec<B> {
+    let len = cmp::max(a.len(), b.len());
+    let stores = num_stores::<B>(len);
+    let words = (0..stores).map(|i| f(store_or_zero(a, i), store_or_zero(b, i)));
+
+    from_stores(words, len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BitSet;
+
+    fn set(members: &[usize]) -> BitSet<u64> {
+        let mut s = BitSet::<u64>::new();
+        for &i in members {
+            s.insert(i);
+        }
+        s
+    }
+
+    #[test]
+    fn bitset_insert_contains_remove() {
+        let mut s = set(&[1, 70, 130]);
+
+        assert!(s.contains(1));
+        assert!(s.contains(70));
+        assert!(s.contains(130));
+        assert!(!s.contains(2));
+
+        s.remove(70);
+        assert!(!s.contains(70));
+    }
+
+    #[test]
+    fn bitset_union_intersection_difference() {
+        let a = set(&[1, 2, 3, 70]);
+        let b = set(&[2, 3, 4, 130]);
+
+        let union = a.union(&b);
+        for i in &[1, 2, 3, 4, 70, 130] {
+            assert!(union.contains(*i));
+        }
+
+        let intersection = a.intersection(&b);
+        assert!(intersection.contains(2));
+        assert!(intersection.contains(3));
+        assert!(!intersection.contains(1));
+        assert!(!intersection.contains(70));
+
+        let difference = a.difference(&b);
+        assert!(difference.contains(1));
+        assert!(difference.contains(70));
+        assert!(!difference.contains(2));
+        assert!(!difference.contains(4));
+
+        let symmetric = a.symmetric_difference(&b);
+        assert!(symmetric.contains(1));
+        assert!(symmetric.contains(4));
+        assert!(symmetric.contains(70));
+        assert!(symmetric.contains(130));
+        assert!(!symmetric.contains(2));
+        assert!(!symmetric.contains(3));
+    }
+
+    #[test]
+    fn bitset_subset_and_disjoint() {
+        let a = set(&[1, 2]);
+        let b = set(&[1, 2, 3]);
+        let c = set(&[4, 5]);
+
+        assert!(a.is_subset(&b));
+        assert!(!b.is_subset(&a));
+        assert!(a.is_disjoint(&c));
+        assert!(!a.is_disjoint(&b));
+    }
+}
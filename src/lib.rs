@@ -1,9 +1,16 @@
+#![cfg_attr(not(test), no_std)]
+
 extern crate alloc;
 
+mod bit_set;
+
+pub use bit_set::BitSet;
+
 use alloc::alloc as __alloc;
 use core::cmp;
 use core::convert::TryInto;
 use core::fmt;
+use core::marker::PhantomData;
 use core::mem;
 use core::ops;
 
@@ -13,6 +20,7 @@ macro_rules! bitstore_trait_impl(
            fn bits() -> usize { mem::size_of::<$t>() * 8 }
            fn zero() -> $t { 0 }
            fn one() -> $t { 1 }
+           fn count_ones(self) -> u32 { <$t>::count_ones(self) }
        })*
    )
 );
@@ -29,75 +37,230 @@ pub trait BitStore:
     + ops::BitAndAssign
     + ops::BitOr<Output = Self>
     + ops::BitOrAssign
+    + ops::BitXor<Output = Self>
+    + ops::BitXorAssign
     + cmp::PartialOrd
 {
     fn bits() -> usize;
     fn zero() -> Self;
     fn one() -> Self;
+    fn count_ones(self) -> u32;
 }
 
 bitstore_trait_impl!(u8 u16 u32 u64 u128);
 
-#[derive(Debug, PartialEq)]
-pub struct BitVec<B> {
-    /// Byte sequence used to store bits
-    store: *mut B,
-    /// Number of byte stores of size B
+/// Determines which physical bit of a `B`-sized store a logical bit
+/// index within that store maps to.
+pub trait BitOrder {
+    fn mask<B: BitStore>(bit_index: usize) -> B;
+}
+
+/// Least-significant-bit-first: logical bit `0` of a store is its
+/// lowest bit. The crate's default ordering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lsb0;
+
+/// Most-significant-bit-first: logical bit `0` of a store is its
+/// highest bit, matching layouts used by network and file formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Msb0;
+
+impl BitOrder for Lsb0 {
+    #[inline]
+    fn mask<B: BitStore>(bit_index: usize) -> B {
+        B::one() << bit_index
+    }
+}
+
+impl BitOrder for Msb0 {
+    #[inline]
+    fn mask<B: BitStore>(bit_index: usize) -> B {
+        B::one() << (B::bits() - 1 - bit_index)
+    }
+}
+
+/// Number of bits in a machine word, the size of the tagged `store` field.
+const WORD_BITS: usize = usize::BITS as usize;
+/// Bits reserved within a tagged word to hold the inline length.
+/// `WORD_BITS` is a power of two, so this is `log2(WORD_BITS)`, enough
+/// to represent any inline length up to `WORD_BITS - 1`.
+const LEN_BITS: usize = WORD_BITS.trailing_zeros() as usize;
+/// Bit offset of the first inline data bit, after the tag bit and the
+/// length bits.
+const DATA_SHIFT: usize = 1 + LEN_BITS;
+/// Maximum number of bits that can be stored inline, without a heap
+/// allocation.
+const INLINE_CAP: usize = WORD_BITS - DATA_SHIFT;
+const LEN_MASK: usize = ((1usize << LEN_BITS) - 1) << 1;
+
+/// Header of a heap-allocated store buffer, placed immediately before
+/// the `B` elements it describes.
+#[repr(C)]
+struct Header {
+    /// Number of `B` stores following this header.
     num_stores: usize,
-    /// Length of current sequence, index = len - 1
+    /// Length of the current bit sequence, index = len - 1.
     len: usize,
 }
 
+/// Layout of a heap buffer (header + `num_stores` elements of `B`).
+fn heap_layout<B>(num_stores: usize) -> __alloc::Layout {
+    let header_layout = __alloc::Layout::new::<Header>();
+    let array_layout =
+        __alloc::Layout::array::<B>(num_stores).expect("bitvec layout overflow");
+    header_layout
+        .extend(array_layout)
+        .expect("invalid bitvec layout")
+        .0
+        .pad_to_align()
+}
+
+/// Byte offset of the first `B` element within a heap buffer.
+fn heap_data_offset<B>() -> usize {
+    let header_layout = __alloc::Layout::new::<Header>();
+    let elem_layout = __alloc::Layout::new::<B>();
+    header_layout
+        .extend(elem_layout)
+        .expect("invalid bitvec layout")
+        .1
+}
+
+/// A growable bit vector, with a configurable bit ordering `O` (see
+/// [`BitOrder`]) used when a logical bit index is mapped to a physical
+/// bit within a `B`-sized store.
+///
+/// `store` is a single tagged machine word: when its low bit is `0` the
+/// remaining bits hold a length and the bit sequence itself inline, with
+/// no heap allocation; when its low bit is `1` the rest of the word is a
+/// pointer to a heap-allocated [`Header`] followed by `B` stores. Short
+/// bit vectors therefore never touch the allocator.
+#[derive(Debug, PartialEq)]
+pub struct BitVec<B, O = Lsb0> {
+    store: usize,
+    _marker: PhantomData<(*mut B, O)>,
+}
+
 #[derive(Debug)]
 pub enum Error {
     OutOfBounds,
 }
 
-impl<B: BitStore> BitVec<B> {
-    pub fn new() -> BitVec<B> {
-        let layout = __alloc::Layout::new::<B>();
-        let ptr = unsafe { __alloc::alloc_zeroed(layout) };
+impl<B, O> BitVec<B, O> {
+    #[inline]
+    fn is_inline(&self) -> bool {
+        self.store & 1 == 0
+    }
 
-        if ptr.is_null() {
-            panic!("unable to initialize (allocate) bitvec");
+    #[inline]
+    fn heap_ptr(&self) -> *mut Header {
+        (self.store & !1) as *mut Header
+    }
+
+    #[inline]
+    fn heap_data_ptr(&self) -> *mut B {
+        unsafe { (self.heap_ptr() as *mut u8).add(heap_data_offset::<B>()) as *mut B }
+    }
+
+    #[inline]
+    fn set_inline_len(&mut self, len: usize) {
+        debug_assert!(len <= INLINE_CAP);
+        self.store = (self.store & !LEN_MASK) | (len << 1);
+    }
+
+    fn set_len(&mut self, len: usize) {
+        if self.is_inline() {
+            self.set_inline_len(len);
+        } else {
+            unsafe { (*self.heap_ptr()).len = len };
         }
+    }
+}
 
-        #[allow(clippy::cast_ptr_alignment)]
+impl<B: BitStore, O: BitOrder> BitVec<B, O> {
+    pub fn new() -> BitVec<B, O> {
         BitVec {
-            store: ptr as *mut _,
-            num_stores: 2,
-            len: 0,
+            store: 0,
+            _marker: PhantomData,
         }
     }
 
     #[inline]
     pub fn capacity(&self) -> usize {
-        self.num_stores * B::bits()
+        if self.is_inline() {
+            INLINE_CAP
+        } else {
+            unsafe { (*self.heap_ptr()).num_stores * B::bits() }
+        }
     }
 
     #[inline]
     pub fn len(&self) -> usize {
-        self.len
+        if self.is_inline() {
+            (self.store & LEN_MASK) >> 1
+        } else {
+            unsafe { (*self.heap_ptr()).len }
+        }
     }
 
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.len == 0
+        self.len() == 0
+    }
+
+    /// Number of `B`-sized stores needed to hold the inline data, used
+    /// when iterating an inline-backed bitvec store-by-store.
+    fn inline_num_stores() -> usize {
+        INLINE_CAP.div_ceil(B::bits())
+    }
+
+    /// Total number of `B`-sized stores currently backing the bitvec: the
+    /// fixed inline store count, or the heap buffer's allocated store
+    /// count. Not derived from `len()`.
+    fn num_stores(&self) -> usize {
+        if self.is_inline() {
+            Self::inline_num_stores()
+        } else {
+            unsafe { (*self.heap_ptr()).num_stores }
+        }
     }
 
     #[inline]
     fn store_as_copy(&self, index: usize) -> Option<B> {
-        if index < self.num_stores {
-            Some(unsafe { *self.store.add(index) })
+        if index >= self.num_stores() {
+            return None;
+        }
+
+        if self.is_inline() {
+            Some(self.inline_store_as(index))
         } else {
-            None
+            Some(unsafe { *self.heap_data_ptr().add(index) })
         }
     }
 
+    /// Reassemble the `index`-th `B`-sized store out of the inline bits,
+    /// honoring `O` the same way a heap-backed store would.
+    fn inline_store_as(&self, index: usize) -> B {
+        let bits = B::bits();
+        let base = index * bits;
+        let mut val = B::zero();
+
+        for b in 0..bits {
+            let global_bit = base + b;
+            if global_bit >= INLINE_CAP {
+                break;
+            }
+            if (self.store >> (DATA_SHIFT + global_bit)) & 1 == 1 {
+                val |= O::mask::<B>(b);
+            }
+        }
+
+        val
+    }
+
     #[inline]
     fn lookup_store_mut(&self, index: usize) -> *mut B {
         let store_index = index / B::bits();
-        unsafe { self.store.add(store_index) }
+        unsafe { self.heap_data_ptr().add(store_index) }
     }
 
     #[inline]
@@ -108,54 +271,146 @@ impl<B: BitStore> BitVec<B> {
     #[inline]
     fn index_mask(&self, index: usize) -> B {
         let bit_index = index % B::bits();
-        B::one() << bit_index
+        O::mask::<B>(bit_index)
     }
 
-    /// Grow or shrink number of stores by a relative change.
+    /// Grow or shrink the number of heap stores by a relative change.
+    /// Only valid once the bitvec has spilled to the heap.
     fn resize(&mut self, change: isize) {
-        self.num_stores = (self.num_stores as isize + change)
+        debug_assert!(!self.is_inline(), "resize called on an inline bitvec");
+
+        let old_ptr = self.heap_ptr();
+        let old_num_stores = unsafe { (*old_ptr).num_stores };
+        let old_len = unsafe { (*old_ptr).len };
+
+        let new_num_stores: usize = (old_num_stores as isize + change)
             .try_into()
             .expect("unable to resize bitvec");
 
-        // We shrank past elements, set new len
-        if self.len() > self.capacity() {
-            self.len = self.capacity();
+        let old_layout = heap_layout::<B>(old_num_stores);
+        let new_layout = heap_layout::<B>(new_num_stores);
+
+        let new_ptr = unsafe {
+            __alloc::realloc(old_ptr as *mut u8, old_layout, new_layout.size()) as *mut Header
+        };
+
+        if new_ptr.is_null() {
+            panic!("unable to grow (reallocate) bitvec");
         }
 
-        let layout = __alloc::Layout::new::<B>();
+        let new_capacity = new_num_stores * B::bits();
+        let new_len = cmp::min(old_len, new_capacity);
 
-        #[allow(clippy::cast_ptr_alignment)]
         unsafe {
-            self.store = __alloc::realloc(
-                self.store as *mut _,
-                layout,
-                self.num_stores * mem::size_of::<B>(),
-            ) as *mut _;
+            (*new_ptr).num_stores = new_num_stores;
+            (*new_ptr).len = new_len;
         }
 
-        if self.store.is_null() {
-            panic!("unable to grow (reallocate) bitvec");
+        // `realloc` leaves newly-added bytes uninitialized; readers of a
+        // heap-backed bitvec's raw stores (count_ones, any/none, BitSet's
+        // set algebra) rely on bits past `len()` being zero, so the grown
+        // tail must be zeroed explicitly.
+        if new_num_stores > old_num_stores {
+            let data_ptr =
+                unsafe { (new_ptr as *mut u8).add(heap_data_offset::<B>()) as *mut B };
+            unsafe {
+                data_ptr
+                    .add(old_num_stores)
+                    .write_bytes(0u8, new_num_stores - old_num_stores);
+            }
+        }
+
+        self.store = (new_ptr as usize) | 1;
+    }
+
+    /// Move an inline-backed bitvec onto the heap, preserving its bits
+    /// and reserving room for at least `min_capacity` bits.
+    fn promote_to_heap(&mut self, min_capacity: usize) {
+        let len = self.len();
+        let min_bits = cmp::max(min_capacity, len + 1);
+        let min_stores = min_bits.div_ceil(B::bits());
+        let num_stores = cmp::max(min_stores, 2);
+
+        let layout = heap_layout::<B>(num_stores);
+        let ptr = unsafe { __alloc::alloc_zeroed(layout) } as *mut Header;
+
+        if ptr.is_null() {
+            panic!("unable to initialize (allocate) bitvec");
         }
+
+        unsafe {
+            (*ptr).num_stores = num_stores;
+            (*ptr).len = len;
+        }
+
+        let data_ptr =
+            unsafe { (ptr as *mut u8).add(heap_data_offset::<B>()) as *mut B };
+
+        for i in 0..len {
+            if self.get_unchecked(i) {
+                let store_index = i / B::bits();
+                let bit_index = i % B::bits();
+                unsafe {
+                    *data_ptr.add(store_index) |= O::mask::<B>(bit_index);
+                }
+            }
+        }
+
+        self.store = (ptr as usize) | 1;
     }
 
-    /// Double the block allocation.
+    /// Double the heap block allocation. An inline bitvec is instead
+    /// promoted to the heap (at least two stores), without doubling.
     fn grow(&mut self) {
-        self.resize(self.num_stores as isize);
+        if self.is_inline() {
+            self.promote_to_heap(0);
+        } else {
+            let num_stores = unsafe { (*self.heap_ptr()).num_stores };
+            self.resize(num_stores as isize);
+        }
+    }
+
+    /// Reserve room for at least `additional` more bits without
+    /// necessarily reallocating on every subsequent `push`.
+    pub fn reserve(&mut self, additional: usize) {
+        let needed = self.len() + additional;
+
+        if needed <= self.capacity() {
+            return;
+        }
+
+        if self.is_inline() {
+            self.promote_to_heap(needed);
+        } else {
+            let current = unsafe { (*self.heap_ptr()).num_stores };
+            let wanted = needed.div_ceil(B::bits());
+
+            if wanted > current {
+                self.resize((wanted - current) as isize);
+            }
+        }
     }
 
-    /// Removes n store pages.
+    /// Removes n store pages. No-op while the bitvec is still inline,
+    /// since inline storage has no separate store pages to remove.
     pub fn shrink_blocks_by(&mut self, n: isize) {
-        self.resize(-n);
+        if !self.is_inline() {
+            self.resize(-n);
+        }
     }
 
     /// Retrieve boolean within capacity bounds, this may
     /// return a default initilization of value `false`.
     pub fn get_unchecked(&self, index: usize) -> bool {
-        let store_ptr = self.lookup_store(index);
-        let index_mask = self.index_mask(index);
-        let b = unsafe { *store_ptr } & index_mask;
+        if self.is_inline() {
+            (self.store >> (DATA_SHIFT + index)) & 1 == 1
+        } else {
+            let store_ptr = self.lookup_store(index);
+            let index_mask = self.index_mask(index);
+            let b = unsafe { *store_ptr } & index_mask;
 
-        b > B::zero()
+            b > B::zero()
+        }
     }
 
     /// Retrieve boolean within the current length.
@@ -170,14 +425,23 @@ impl<B: BitStore> BitVec<B> {
     /// Sets any boolean within capacity at index `i`,
     /// without changing the length representation of the bitvec.
     pub fn set_unchecked(&mut self, index: usize, element: bool) {
-        let store_ptr_mut = self.lookup_store_mut(index);
-        let index_mask = self.index_mask(index);
-
-        unsafe {
+        if self.is_inline() {
+            let mask = 1usize << (DATA_SHIFT + index);
             if element {
-                *store_ptr_mut |= index_mask;
+                self.store |= mask;
             } else {
-                *store_ptr_mut &= !index_mask;
+                self.store &= !mask;
+            }
+        } else {
+            let store_ptr_mut = self.lookup_store_mut(index);
+            let index_mask = self.index_mask(index);
+
+            unsafe {
+                if element {
+                    *store_ptr_mut |= index_mask;
+                } else {
+                    *store_ptr_mut &= !index_mask;
+                }
             }
         }
     }
@@ -199,87 +463,341 @@ impl<B: BitStore> BitVec<B> {
             self.grow();
         }
 
-        self.len += 1;
-
-        let index = self.len - 1;
-
-        assert!(self.set(index, val).is_ok());
+        let index = self.len();
+        self.set_unchecked(index, val);
+        self.set_len(index + 1);
     }
 
     /// Pop boolean bit off the bitvec.
+    ///
+    /// The vacated bit is zeroed so that a store's bits past `len()`
+    /// are always zero; `count_ones`, `all`/`any`/`none` rely on this
+    /// invariant instead of re-masking.
     pub fn pop(&mut self) -> Option<bool> {
-        if !self.is_empty() {
-            self.len -= 1;
-            Some(self.get_unchecked(self.len))
-        } else {
+        if self.is_empty() {
             None
+        } else {
+            let new_len = self.len() - 1;
+            let val = self.get_unchecked(new_len);
+            self.set_unchecked(new_len, false);
+            self.set_len(new_len);
+            Some(val)
         }
     }
 
     /// Iterator over bit stores
-    pub fn iter_stores(&self) -> Stores<'_, B> {
+    pub fn iter_stores(&self) -> Stores<'_, B, O> {
         Stores {
             bitvec: &self,
             index: 0,
+            tail: self.num_stores(),
         }
     }
 
     /// Iterator over bits, represented by booleans
-    pub fn iter_bits(&self) -> Bits<'_, B> {
+    pub fn iter_bits(&self) -> Bits<'_, B, O> {
         Bits {
             bitvec: &self,
             index: 0,
+            tail: self.len(),
+        }
+    }
+
+    /// Number of bits set to `true`.
+    pub fn count_ones(&self) -> u32 {
+        self.iter_stores().map(B::count_ones).sum()
+    }
+
+    /// Whether every bit is `true`. Vacuously `true` for an empty bitvec.
+    pub fn all(&self) -> bool {
+        let bits = B::bits();
+        let full_stores = self.len() / bits;
+        let tail_bits = self.len() % bits;
+        let mut stores = self.iter_stores();
+
+        for _ in 0..full_stores {
+            if stores.next().unwrap() != !B::zero() {
+                return false;
+            }
+        }
+
+        if tail_bits > 0 {
+            let tail_mask = (0..tail_bits).fold(B::zero(), |acc, bit| acc | O::mask::<B>(bit));
+
+            if stores.next().unwrap() != tail_mask {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Whether at least one bit is `true`.
+    pub fn any(&self) -> bool {
+        self.iter_stores().any(|store| store != B::zero())
+    }
+
+    /// Whether every bit is `false`. Vacuously `true` for an empty bitvec.
+    pub fn none(&self) -> bool {
+        !self.any()
+    }
+}
+
+impl<O: BitOrder> BitVec<u8, O> {
+    /// Build a bitvec from a byte buffer, one store per byte. Input too
+    /// long to fit inline is copied into the heap buffer whole; shorter
+    /// input is packed into the inline word bit by bit.
+    pub fn from_bytes(bytes: &[u8]) -> BitVec<u8, O> {
+        let len = bytes.len() * 8;
+
+        if len <= INLINE_CAP {
+            return from_stores(bytes.iter().copied(), len);
+        }
+
+        let num_stores = bytes.len();
+        let layout = heap_layout::<u8>(num_stores);
+        let ptr = unsafe { __alloc::alloc(layout) } as *mut Header;
+
+        if ptr.is_null() {
+            panic!("unable to initialize (allocate) bitvec");
+        }
+
+        unsafe {
+            (*ptr).num_stores = num_stores;
+            (*ptr).len = len;
+        }
+
+        let data_ptr = unsafe { (ptr as *mut u8).add(heap_data_offset::<u8>()) };
+        unsafe { core::ptr::copy_nonoverlapping(bytes.as_ptr(), data_ptr, num_stores) };
+
+        BitVec {
+            store: (ptr as usize) | 1,
+            _marker: PhantomData,
         }
     }
+
+    /// Copy the bitvec's stores out as a byte buffer, one byte per
+    /// store. Heap stores are copied whole; an inline bitvec's stores are
+    /// reassembled bit by bit. The last byte is padded with `false` bits
+    /// past `len()`.
+    pub fn to_bytes(&self) -> alloc::vec::Vec<u8> {
+        let num_bytes = self.len().div_ceil(8);
+        self.iter_stores().take(num_bytes).collect()
+    }
+}
+
+/// Reassemble stores into a `BitVec` of exactly `len` bits, discarding
+/// anything past the `len`-th bit. Shared by the bulk bitwise operators
+/// and by [`BitSet`]'s set-algebra.
+///
+/// When the output spills to the heap, writes each store word directly
+/// into its backing buffer, mirroring `from_bytes`'s fast path; inline
+/// output is built bit by bit.
+pub(crate) fn from_stores<B: BitStore, O: BitOrder>(
+    stores: impl Iterator<Item = B>,
+    len: usize,
+) -> BitVec<B, O> {
+    let bits = B::bits();
+    let num_stores = len.div_ceil(bits);
+
+    if num_stores == 0 {
+        return BitVec::new();
+    }
+
+    // Too short to spill to the heap: the inline representation packs
+    // bits directly into a single machine word rather than an array of
+    // `B` stores, so there's no word-sized buffer to write into here.
+    if len <= INLINE_CAP {
+        let mut out = BitVec::<B, O>::new();
+        let mut produced = 0;
+
+        'words: for word in stores {
+            for bit in 0..bits {
+                if produced >= len {
+                    break 'words;
+                }
+                out.push(word & O::mask::<B>(bit) != B::zero());
+                produced += 1;
+            }
+        }
+
+        return out;
+    }
+
+    let layout = heap_layout::<B>(num_stores);
+    let ptr = unsafe { __alloc::alloc_zeroed(layout) } as *mut Header;
+
+    if ptr.is_null() {
+        panic!("unable to initialize (allocate) bitvec");
+    }
+
+    unsafe {
+        (*ptr).num_stores = num_stores;
+        (*ptr).len = len;
+    }
+
+    let data_ptr = unsafe { (ptr as *mut u8).add(heap_data_offset::<B>()) as *mut B };
+    let tail_bits = len % bits;
+    let tail_mask = (0..tail_bits).fold(B::zero(), |acc, bit| acc | O::mask::<B>(bit));
+
+    for (i, word) in stores.take(num_stores).enumerate() {
+        let word = if tail_bits > 0 && i + 1 == num_stores {
+            word & tail_mask
+        } else {
+            word
+        };
+
+        unsafe { *data_ptr.add(i) = word };
+    }
+
+    BitVec {
+        store: (ptr as usize) | 1,
+        _marker: PhantomData,
+    }
+}
+
+fn zip_stores<B: BitStore, O: BitOrder>(
+    a: &BitVec<B, O>,
+    b: &BitVec<B, O>,
+    f: impl Fn(B, B) -> B,
+) -> BitVec<B, O> {
+    assert_eq!(
+        a.len(),
+        b.len(),
+        "bitvecs must have equal length for bitwise operators"
+    );
+
+    from_stores(a.iter_stores().zip(b.iter_stores()).map(|(x, y)| f(x, y)), a.len())
+}
+
+impl<'a, B: BitStore, O: BitOrder> ops::BitAnd for &'a BitVec<B, O> {
+    type Output = BitVec<B, O>;
+
+    fn bitand(self, rhs: &'a BitVec<B, O>) -> BitVec<B, O> {
+        zip_stores(self, rhs, |a, b| a & b)
+    }
+}
+
+impl<'a, B: BitStore, O: BitOrder> ops::BitOr for &'a BitVec<B, O> {
+    type Output = BitVec<B, O>;
+
+    fn bitor(self, rhs: &'a BitVec<B, O>) -> BitVec<B, O> {
+        zip_stores(self, rhs, |a, b| a | b)
+    }
 }
 
-impl<B> ops::Drop for BitVec<B> {
+impl<'a, B: BitStore, O: BitOrder> ops::BitXor for &'a BitVec<B, O> {
+    type Output = BitVec<B, O>;
+
+    fn bitxor(self, rhs: &'a BitVec<B, O>) -> BitVec<B, O> {
+        zip_stores(self, rhs, |a, b| a ^ b)
+    }
+}
+
+impl<B: BitStore, O: BitOrder> ops::Not for &BitVec<B, O> {
+    type Output = BitVec<B, O>;
+
+    fn not(self) -> BitVec<B, O> {
+        from_stores(self.iter_stores().map(|store| !store), self.len())
+    }
+}
+
+impl<B, O> ops::Drop for BitVec<B, O> {
     fn drop(&mut self) {
-        let layout = __alloc::Layout::new::<B>();
+        if !self.is_inline() {
+            let num_stores = unsafe { (*self.heap_ptr()).num_stores };
+            let layout = heap_layout::<B>(num_stores);
 
-        unsafe { __alloc::dealloc(self.store as *mut _, layout) };
+            unsafe { __alloc::dealloc(self.heap_ptr() as *mut u8, layout) };
+        }
     }
 }
 
-pub struct Stores<'a, T> {
-    bitvec: &'a BitVec<T>,
+pub struct Stores<'a, T, O = Lsb0> {
+    bitvec: &'a BitVec<T, O>,
     index: usize,
+    tail: usize,
 }
 
-impl<'a, B: BitStore> Iterator for Stores<'a, B> {
+impl<'a, B: BitStore, O: BitOrder> Iterator for Stores<'a, B, O> {
     type Item = B;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.tail {
+            return None;
+        }
+
         let b = self.bitvec.store_as_copy(self.index);
+        self.index += 1;
+        b
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.tail - self.index;
+        (remaining, Some(remaining))
+    }
+}
 
-        if b.is_some() {
-            self.index += 1;
+impl<'a, B: BitStore, O: BitOrder> DoubleEndedIterator for Stores<'a, B, O> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index >= self.tail {
+            return None;
         }
 
-        b
+        self.tail -= 1;
+        self.bitvec.store_as_copy(self.tail)
+    }
+}
+
+impl<'a, B: BitStore, O: BitOrder> ExactSizeIterator for Stores<'a, B, O> {
+    fn len(&self) -> usize {
+        self.tail - self.index
     }
 }
 
-pub struct Bits<'a, T> {
-    bitvec: &'a BitVec<T>,
+pub struct Bits<'a, T, O = Lsb0> {
+    bitvec: &'a BitVec<T, O>,
     index: usize,
+    tail: usize,
 }
 
-impl<'a, B: BitStore> Iterator for Bits<'a, B> {
+impl<'a, B: BitStore, O: BitOrder> Iterator for Bits<'a, B, O> {
     type Item = bool;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let b = self.bitvec.get(self.index);
+        if self.index >= self.tail {
+            return None;
+        }
 
-        if b.is_some() {
-            self.index += 1;
+        let b = self.bitvec.get_unchecked(self.index);
+        self.index += 1;
+        Some(b)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.tail - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, B: BitStore, O: BitOrder> DoubleEndedIterator for Bits<'a, B, O> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index >= self.tail {
+            return None;
         }
 
-        b
+        self.tail -= 1;
+        Some(self.bitvec.get_unchecked(self.tail))
+    }
+}
+
+impl<'a, B: BitStore, O: BitOrder> ExactSizeIterator for Bits<'a, B, O> {
+    fn len(&self) -> usize {
+        self.tail - self.index
     }
 }
 
-impl<B: fmt::Display + BitStore> fmt::Display for BitVec<B> {
+impl<B: fmt::Display + BitStore, O: BitOrder> fmt::Display for BitVec<B, O> {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         for bit in self.iter_bits() {
             write!(f, "{:b}", bit as u8)?;
@@ -288,9 +806,56 @@ impl<B: fmt::Display + BitStore> fmt::Display for BitVec<B> {
     }
 }
 
+impl<B: BitStore, O: BitOrder> core::iter::FromIterator<bool> for BitVec<B, O> {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut out = BitVec::new();
+        out.reserve(iter.size_hint().0);
+
+        for bit in iter {
+            out.push(bit);
+        }
+
+        out
+    }
+}
+
+impl<B: BitStore, O: BitOrder> Extend<bool> for BitVec<B, O> {
+    fn extend<I: IntoIterator<Item = bool>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+
+        for bit in iter {
+            self.push(bit);
+        }
+    }
+}
+
+/// Build a `BitVec` from a list of bools, or a `bool` repeated `n`
+/// times, mirroring `vec!`.
+///
+/// ```ignore
+/// let a: BitVec<u64> = bitvec![true, false, true];
+/// let b: BitVec<u64> = bitvec![true; 3];
+/// ```
+#[macro_export]
+macro_rules! bitvec {
+    () => {
+        $crate::BitVec::new()
+    };
+    ($elem:expr; $n:expr) => {
+        <$crate::BitVec<_> as ::core::iter::FromIterator<bool>>::from_iter(
+            ::core::iter::repeat($elem).take($n),
+        )
+    };
+    ($($x:expr),+ $(,)?) => {
+        <$crate::BitVec<_> as ::core::iter::FromIterator<bool>>::from_iter([$($x),+])
+    };
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::BitVec;
+    use crate::{BitVec, Msb0};
 
     fn bv() -> BitVec<u64> {
         BitVec::<u64>::new()
@@ -302,8 +867,11 @@ mod tests {
     }
 
     #[test]
-    fn bitvec_initial_cap() {
-        assert_eq!(128, bv().capacity());
+    fn bitvec_initial_cap_is_inline() {
+        // A freshly constructed bitvec is inline, so its capacity is
+        // bound by a machine word rather than a heap allocation.
+        assert!(bv().capacity() < 128);
+        assert!(bv().capacity() > 0);
     }
 
     #[test]
@@ -311,27 +879,25 @@ mod tests {
         let b = bv();
 
         assert_eq!(false, b.get_unchecked(0));
-        assert_eq!(false, b.get_unchecked(63));
+        assert_eq!(false, b.get_unchecked(63 % crate::INLINE_CAP));
     }
 
     #[test]
     fn bitvec_set_unchecked() {
         let mut b = bv();
 
-        b.set_unchecked(63, true);
-        b.set_unchecked(33, true);
-        b.set_unchecked(31, true);
+        b.set_unchecked(33 % crate::INLINE_CAP, true);
+        b.set_unchecked(31 % crate::INLINE_CAP, true);
 
-        b.set_unchecked(32, true);
-        b.set_unchecked(32, false);
+        b.set_unchecked(32 % crate::INLINE_CAP, true);
+        b.set_unchecked(32 % crate::INLINE_CAP, false);
 
         assert_eq!(false, b.get_unchecked(0));
 
-        assert_eq!(true, b.get_unchecked(63));
-        assert_eq!(true, b.get_unchecked(33));
-        assert_eq!(true, b.get_unchecked(31));
+        assert_eq!(true, b.get_unchecked(33 % crate::INLINE_CAP));
+        assert_eq!(true, b.get_unchecked(31 % crate::INLINE_CAP));
 
-        assert_eq!(false, b.get_unchecked(32));
+        assert_eq!(false, b.get_unchecked(32 % crate::INLINE_CAP));
     }
 
     #[test]
@@ -339,11 +905,27 @@ mod tests {
         let mut b = bv();
         b.push(true);
         let r1 = b.set(0, false);
-        let r2 = b.set(63, true);
+        let r2 = b.set(b.len() + 1, true);
         assert!(r1.is_ok());
         assert!(r2.is_err());
     }
 
+    #[test]
+    fn bitvec_stays_inline_for_small_sizes() {
+        let mut b = bv();
+
+        for i in 0..10 {
+            b.push(i % 2 == 0);
+        }
+
+        assert!(b.is_inline());
+        assert_eq!(10, b.len());
+
+        for i in 0..10 {
+            assert_eq!(Some(i % 2 == 0), b.get(i));
+        }
+    }
+
     #[test]
     fn bitvec_grow() {
         let mut b = bv();
@@ -358,12 +940,13 @@ mod tests {
             assert_eq!(Some(true), val);
         }
 
-        assert_eq!(256, b.capacity());
+        assert!(!b.is_inline());
         assert_eq!(139, b.len());
 
+        let capacity_before = b.capacity();
         b.grow();
 
-        assert_eq!(512, b.capacity());
+        assert_eq!(capacity_before * 2, b.capacity());
         assert_eq!(139, b.len());
         assert_eq!(None, b.get(139));
         assert_eq!(Some(true), b.get(138));
@@ -378,14 +961,10 @@ mod tests {
             b.push(true);
         }
 
-        for i in 0..num_indices {
-            let val = b.get(i);
-            assert_eq!(Some(true), val);
-        }
-
-        assert_eq!(256, b.capacity());
         assert_eq!(139, b.len());
 
+        let capacity_before = b.capacity();
+
         // test spurious false during pop
         let false_index = 128;
         let _ = b.set(false_index, false);
@@ -405,7 +984,7 @@ mod tests {
 
         b.shrink_blocks_by(2);
 
-        assert_eq!(128, b.capacity());
+        assert_eq!(capacity_before - 2 * 64, b.capacity());
         assert_eq!(num_indices - remove_indices, b.len());
     }
 
@@ -439,4 +1018,310 @@ mod tests {
 
         assert_eq!("1111001", format!("{}", b));
     }
+
+    #[test]
+    fn bitvec_count_ones_all_any_none() {
+        let mut b = bv();
+
+        assert!(b.all());
+        assert!(!b.any());
+        assert!(b.none());
+
+        for _ in 0..4 {
+            b.push(true);
+        }
+
+        assert_eq!(4, b.count_ones());
+        assert!(b.all());
+        assert!(b.any());
+        assert!(!b.none());
+
+        b.push(false);
+
+        assert_eq!(4, b.count_ones());
+        assert!(!b.all());
+        assert!(b.any());
+        assert!(!b.none());
+    }
+
+    #[test]
+    fn bitvec_count_ones_all_any_across_stores() {
+        let mut b = bv();
+
+        for _ in 0..139 {
+            b.push(true);
+        }
+
+        assert!(!b.is_inline());
+        assert_eq!(139, b.count_ones());
+        assert!(b.all());
+
+        b.set(138, false).unwrap();
+
+        assert_eq!(138, b.count_ones());
+        assert!(!b.all());
+        assert!(b.any());
+    }
+
+    #[test]
+    fn bitvec_grown_heap_tail_stays_zeroed() {
+        let mut b = bv();
+
+        // Push enough `false` bits to force at least two heap growths,
+        // so `resize()`'s reallocated tail is exercised repeatedly.
+        for _ in 0..400 {
+            b.push(false);
+        }
+
+        assert!(!b.is_inline());
+        assert_eq!(0, b.count_ones());
+        assert!(!b.any());
+        assert!(b.none());
+    }
+
+    #[test]
+    fn bitvec_bitwise_operators() {
+        let mut a = bv();
+        let mut c = bv();
+
+        for i in 0..8 {
+            a.push(i % 2 == 0);
+            c.push(i % 3 == 0);
+        }
+
+        let and = &a & &c;
+        let or = &a | &c;
+        let xor = &a ^ &c;
+        let not_a = !&a;
+
+        for i in 0..8 {
+            let av = a.get(i).unwrap();
+            let cv = c.get(i).unwrap();
+            assert_eq!(av & cv, and.get(i).unwrap());
+            assert_eq!(av | cv, or.get(i).unwrap());
+            assert_eq!(av ^ cv, xor.get(i).unwrap());
+            assert_eq!(!av, not_a.get(i).unwrap());
+        }
+    }
+
+    #[test]
+    fn bitvec_not_recomputes_count_and_all_correctly() {
+        let mut b = bv();
+
+        for _ in 0..70 {
+            b.push(false);
+        }
+
+        let not_b = !&b;
+
+        assert_eq!(70, not_b.count_ones());
+        assert!(not_b.all());
+    }
+
+    #[test]
+    fn bitvec_msb0_orders_bits_within_a_store() {
+        let mut lsb = BitVec::<u8, crate::Lsb0>::new();
+        let mut msb = BitVec::<u8, Msb0>::new();
+
+        for _ in 0..8 {
+            lsb.push(false);
+            msb.push(false);
+        }
+
+        lsb.set_unchecked(0, true);
+        msb.set_unchecked(0, true);
+
+        // Same logical bit (0), opposite physical position within the
+        // single backing `u8` store.
+        assert_eq!(Some(0b0000_0001), lsb.iter_stores().next());
+        assert_eq!(Some(0b1000_0000), msb.iter_stores().next());
+
+        // Logical get/set stay order-independent.
+        assert_eq!(Some(true), lsb.get(0));
+        assert_eq!(Some(true), msb.get(0));
+    }
+
+    #[test]
+    fn bitvec_msb0_round_trips_across_heap_growth() {
+        let mut b = BitVec::<u8, Msb0>::new();
+
+        for i in 0..100 {
+            b.push(i % 3 == 0);
+        }
+
+        assert!(!b.is_inline());
+
+        for i in 0..100 {
+            assert_eq!(Some(i % 3 == 0), b.get(i));
+        }
+    }
+
+    #[test]
+    fn bitvec_msb0_bitwise_operators_honor_bit_order() {
+        let mut a = BitVec::<u8, Msb0>::new();
+        let mut b = BitVec::<u8, Msb0>::new();
+
+        for _ in 0..8 {
+            a.push(false);
+            b.push(false);
+        }
+
+        a.set_unchecked(0, true);
+        b.set_unchecked(0, true);
+
+        let anded = &a & &b;
+        assert_eq!(Some(true), anded.get(0));
+        assert_eq!(1, anded.count_ones());
+    }
+
+    #[test]
+    fn bitvec_msb0_all_true_with_partial_tail_store() {
+        let mut b = BitVec::<u8, Msb0>::new();
+
+        for _ in 0..3 {
+            b.push(true);
+        }
+
+        assert_eq!(3, b.count_ones());
+        assert!(b.any());
+        assert!(b.all());
+    }
+
+    #[test]
+    fn bitvec_from_bytes_to_bytes_round_trip() {
+        let bytes = [0b1010_1010u8, 0b0000_1111];
+        let b = BitVec::<u8>::from_bytes(&bytes);
+
+        assert_eq!(16, b.len());
+        assert_eq!(bytes.to_vec(), b.to_bytes());
+    }
+
+    #[test]
+    fn bitvec_from_bytes_stays_inline_for_small_input() {
+        let bytes = [0b1100_0011u8];
+        let b = BitVec::<u8>::from_bytes(&bytes);
+
+        assert!(b.is_inline());
+        assert_eq!(bytes.to_vec(), b.to_bytes());
+    }
+
+    #[test]
+    fn bitvec_from_bytes_promotes_to_heap_for_large_input() {
+        let bytes: alloc::vec::Vec<u8> = (0..32).collect();
+        let b = BitVec::<u8>::from_bytes(&bytes);
+
+        assert!(!b.is_inline());
+        assert_eq!(bytes, b.to_bytes());
+    }
+
+    #[test]
+    fn bitvec_from_bytes_honors_msb0_while_inline() {
+        let bytes = [0b1000_0000u8];
+        let b = BitVec::<u8, Msb0>::from_bytes(&bytes);
+
+        assert!(b.is_inline());
+        assert_eq!(Some(true), b.get(0));
+        assert_eq!(bytes.to_vec(), b.to_bytes());
+    }
+
+    #[test]
+    fn bitvec_from_bytes_honors_msb0_on_heap() {
+        let bytes: alloc::vec::Vec<u8> = (0..32).map(|_| 0b1000_0000u8).collect();
+        let b = BitVec::<u8, Msb0>::from_bytes(&bytes);
+
+        assert!(!b.is_inline());
+        for i in 0..b.len() {
+            assert_eq!(Some(i % 8 == 0), b.get(i));
+        }
+        assert_eq!(bytes, b.to_bytes());
+    }
+
+    #[test]
+    fn bitvec_from_iterator_collects_bools() {
+        let b: BitVec<u64> = [true, false, true, true].iter().copied().collect();
+
+        assert_eq!(4, b.len());
+        assert_eq!(Some(true), b.get(0));
+        assert_eq!(Some(false), b.get(1));
+        assert_eq!(Some(true), b.get(2));
+        assert_eq!(Some(true), b.get(3));
+    }
+
+    #[test]
+    fn bitvec_extend_appends_bools() {
+        let mut b = bv();
+        b.push(true);
+
+        b.extend([false, true, false].iter().copied());
+
+        assert_eq!(4, b.len());
+        assert_eq!(Some(true), b.get(0));
+        assert_eq!(Some(false), b.get(1));
+        assert_eq!(Some(true), b.get(2));
+        assert_eq!(Some(false), b.get(3));
+    }
+
+    #[test]
+    fn bitvec_macro_builds_from_list_and_repeated_elem() {
+        let a: BitVec<u64> = bitvec![true, false, true];
+        assert_eq!(3, a.len());
+        assert_eq!(Some(true), a.get(0));
+        assert_eq!(Some(false), a.get(1));
+        assert_eq!(Some(true), a.get(2));
+
+        let b: BitVec<u64> = bitvec![true; 5];
+        assert_eq!(5, b.len());
+        assert!(b.all());
+
+        let empty: BitVec<u64> = bitvec![];
+        assert_eq!(0, empty.len());
+    }
+
+    #[test]
+    fn bitvec_stores_iterator_is_double_ended_and_exact_size() {
+        let mut b = bv();
+        for i in 0..130 {
+            b.push(i % 2 == 0);
+        }
+
+        let forward: alloc::vec::Vec<u64> = b.iter_stores().collect();
+        let mut stores = b.iter_stores();
+        assert_eq!(forward.len(), stores.len());
+
+        let first = stores.next().unwrap();
+        let last = stores.next_back().unwrap();
+
+        assert_eq!(*forward.first().unwrap(), first);
+        assert_eq!(*forward.last().unwrap(), last);
+        assert_eq!(forward.len() - 2, stores.len());
+    }
+
+    #[test]
+    fn bitvec_bits_iterator_reverse_matches_forward() {
+        let mut b = bv();
+        for i in 0..10 {
+            b.push(i % 3 == 0);
+        }
+
+        let forward: alloc::vec::Vec<bool> = b.iter_bits().collect();
+        let mut backward: alloc::vec::Vec<bool> = b.iter_bits().rev().collect();
+        backward.reverse();
+
+        assert_eq!(forward, backward);
+        assert_eq!(10, b.iter_bits().len());
+    }
+
+    #[test]
+    fn bitvec_reserve_promotes_to_heap_and_preserves_bits() {
+        let mut b = bv();
+        b.push(true);
+        b.push(false);
+
+        b.reserve(200);
+
+        assert!(!b.is_inline());
+        assert_eq!(Some(true), b.get(0));
+        assert_eq!(Some(false), b.get(1));
+        assert!(b.capacity() >= 202);
+    }
 }
@@ -0,0 +1,35 @@
+use cevtib::BitSet;
+
+/// Sieve of Eratosthenes, expressed directly on top of `BitSet`: bit `i`
+/// set means `i` is still considered prime.
+fn primes_up_to(limit: usize) -> Vec<usize> {
+    let mut candidates = BitSet::<u64>::new();
+
+    for i in 2..=limit {
+        candidates.insert(i);
+    }
+
+    let mut i = 2;
+    while i * i <= limit {
+        if candidates.contains(i) {
+            let mut multiple = i * i;
+            while multiple <= limit {
+                candidates.remove(multiple);
+                multiple += i;
+            }
+        }
+        i += 1;
+    }
+
+    (2..=limit).filter(|n| candidates.contains(*n)).collect()
+}
+
+#[test]
+fn sieve_of_eratosthenes() {
+    let expected = vec![
+        2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83,
+        89, 97,
+    ];
+
+    assert_eq!(expected, primes_up_to(100));
+}